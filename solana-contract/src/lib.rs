@@ -4,7 +4,8 @@
 //! 1. Initialize a global network configuration.
 //! 2. Register approved browser nodes (real user devices).
 //! 3. Accept task submissions from those nodes, recording the work hash and reward weight.
-//! 4. Settle rewards by decreasing pending balances once tokens are paid out off-chain.
+//! 4. Settle rewards on-chain by paying out SPL tokens from a program-owned vault and
+//!    decreasing pending balances atomically, or mark them settled administratively.
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
@@ -12,22 +13,30 @@ use solana_program::{
     clock::Clock,
     entrypoint,
     entrypoint::ProgramResult,
-    msg,
-    program::{invoke_signed},
+    keccak, msg,
+    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
     sysvar::Sysvar,
 };
+use spl_token::state::Account as TokenAccount;
 use thiserror::Error;
 
+mod validation;
+use validation::{assert_distinct_keys, assert_owned_by};
+
 /// PDA seed for the single network configuration account.
 const CONFIG_SEED: &[u8] = b"config";
 /// PDA seed prefix for per-node accounts.
 const NODE_SEED: &[u8] = b"node";
 /// PDA seed prefix for individual task submission records.
 const TASK_SEED: &[u8] = b"task";
+/// PDA seed for the program-owned reward vault authority.
+const VAULT_SEED: &[u8] = b"vault";
+/// PDA seed prefix for batched task submission records.
+const BATCH_SEED: &[u8] = b"batch";
 
 entrypoint!(process_instruction);
 
@@ -41,9 +50,21 @@ pub fn process_instruction(
         .map_err(|_| ProgramError::InvalidInstructionData)?;
 
     match instruction {
-        MinionInstruction::InitNetwork { authority, reward_mint } => {
-            process_init_network(program_id, accounts, authority, reward_mint)
-        }
+        MinionInstruction::InitNetwork {
+            authority,
+            reward_mint,
+            units_per_token,
+            claim_cooldown_secs,
+            min_retention_secs,
+        } => process_init_network(
+            program_id,
+            accounts,
+            authority,
+            reward_mint,
+            units_per_token,
+            claim_cooldown_secs,
+            min_retention_secs,
+        ),
         MinionInstruction::RegisterNode => process_register_node(program_id, accounts),
         MinionInstruction::SubmitTask {
             task_hash,
@@ -52,6 +73,41 @@ pub fn process_instruction(
         MinionInstruction::ClaimReward { amount } => {
             process_claim_reward(program_id, accounts, amount)
         }
+        MinionInstruction::PayoutReward { reward_units } => {
+            process_payout_reward(program_id, accounts, reward_units)
+        }
+        MinionInstruction::ClaimByNode { reward_units } => {
+            process_claim_by_node(program_id, accounts, reward_units)
+        }
+        MinionInstruction::ProposeAuthority { new_authority } => {
+            process_propose_authority(program_id, accounts, new_authority)
+        }
+        MinionInstruction::AcceptAuthority => process_accept_authority(program_id, accounts),
+        MinionInstruction::CloseTask => process_close_task(program_id, accounts),
+        MinionInstruction::SubmitTaskBatch {
+            merkle_root,
+            leaf_count,
+            total_reward_units,
+        } => process_submit_task_batch(
+            program_id,
+            accounts,
+            merkle_root,
+            leaf_count,
+            total_reward_units,
+        ),
+        MinionInstruction::ProveTask {
+            task_hash,
+            reward_units,
+            proof,
+            sibling_on_right,
+        } => process_prove_task(
+            program_id,
+            accounts,
+            task_hash,
+            reward_units,
+            proof,
+            sibling_on_right,
+        ),
     }
 }
 
@@ -67,8 +123,14 @@ pub enum MinionInstruction {
     InitNetwork {
         /// Authority allowed to manage the network (usually MinionLab ops).
         authority: Pubkey,
-        /// SPL token mint used for off-chain reward settlement.
+        /// SPL token mint used for reward settlement.
         reward_mint: Pubkey,
+        /// Number of `pending_reward_units` that convert to one base unit of `reward_mint`.
+        units_per_token: u64,
+        /// Minimum seconds between successive `ClaimByNode` calls for the same node.
+        claim_cooldown_secs: i64,
+        /// Minimum seconds a `TaskRecord` must exist before it can be closed via `CloseTask`.
+        min_retention_secs: i64,
     },
 
     /// Register a new node PDA under the authority.
@@ -106,6 +168,104 @@ pub enum MinionInstruction {
         /// Amount of reward units to clear from the node's pending balance.
         amount: u64,
     },
+
+    /// Pay out accrued rewards on-chain from the program-owned vault and clear the
+    /// corresponding pending balance. Settlement and accounting update are atomic.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` Network authority.
+    /// 1. `[]` Network config PDA.
+    /// 2. `[writable]` Node PDA.
+    /// 3. `[writable]` Vault token account (owned by the vault authority PDA).
+    /// 4. `[writable]` Destination token account (owned by the node).
+    /// 5. `[]` Reward mint (must match `NetworkConfig.reward_mint`).
+    /// 6. `[]` Vault authority PDA, seeded by `b"vault"`.
+    /// 7. `[]` SPL Token program.
+    PayoutReward {
+        /// Reward units to settle; converted to token base units via `units_per_token`.
+        reward_units: u64,
+    },
+
+    /// Let a node withdraw its own accrued rewards without authority involvement, rate
+    /// limited by `NetworkConfig.claim_cooldown_secs`.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` Node identity (must match `NodeAccount.node_identity`).
+    /// 1. `[]` Network config PDA.
+    /// 2. `[writable]` Node PDA.
+    /// 3. `[writable]` Vault token account (owned by the vault authority PDA).
+    /// 4. `[writable]` Destination token account (owned by the node).
+    /// 5. `[]` Reward mint (must match `NetworkConfig.reward_mint`).
+    /// 6. `[]` Vault authority PDA, seeded by `b"vault"`.
+    /// 7. `[]` SPL Token program.
+    ClaimByNode {
+        /// Reward units to settle; converted to token base units via `units_per_token`.
+        reward_units: u64,
+    },
+
+    /// Propose a successor network authority. Takes effect only once the successor
+    /// signs `AcceptAuthority`, so a fat-fingered `new_authority` can never brick the
+    /// network.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` Current network authority.
+    /// 1. `[writable]` Network config PDA.
+    ProposeAuthority {
+        /// Candidate successor authority.
+        new_authority: Pubkey,
+    },
+
+    /// Complete a proposed authority handover. Must be signed by the pending authority.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` Pending authority (the proposed successor).
+    /// 1. `[writable]` Network config PDA.
+    AcceptAuthority,
+
+    /// Reclaim the rent from a `TaskRecord` once `NetworkConfig.min_retention_secs`
+    /// has elapsed since `submitted_at`. Whether the node's rewards have been settled
+    /// is not checked.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` Either the task's `node_identity`, or the network authority.
+    /// 1. `[]` Network config PDA.
+    /// 2. `[writable]` Task record PDA (closed).
+    /// 3. `[writable]` Refund recipient (receives the reclaimed lamports).
+    CloseTask,
+
+    /// Commit a whole batch of task completions as a single account, crediting
+    /// `pending_reward_units` once for the entire batch instead of once per task.
+    ///
+    /// Accounts:
+    /// 0. `[signer]` Node identity (must match registered node).
+    /// 1. `[writable]` Node PDA.
+    /// 2. `[writable]` Network config PDA.
+    /// 3. `[writable]` Batch record PDA (created).
+    /// 4. `[]` System program.
+    SubmitTaskBatch {
+        /// Root of the Merkle tree over every task leaf in the batch.
+        merkle_root: [u8; 32],
+        /// Number of leaves (tasks) committed under `merkle_root`.
+        leaf_count: u32,
+        /// Total reward units credited for the whole batch.
+        total_reward_units: u64,
+    },
+
+    /// Verify that a single task was part of a previously committed batch, for disputes.
+    /// Performs no state mutation; fails if the recomputed root does not match.
+    ///
+    /// Accounts:
+    /// 0. `[]` Batch record PDA.
+    ProveTask {
+        /// Hash/fingerprint of the disputed task.
+        task_hash: [u8; 32],
+        /// Reward units claimed for this task.
+        reward_units: u64,
+        /// Sibling hashes from leaf to root.
+        proof: Vec<[u8; 32]>,
+        /// Bit `i` set means sibling `i` is the right-hand node when folding upward.
+        sibling_on_right: u64,
+    },
 }
 
 /// Global network state.
@@ -115,11 +275,19 @@ pub struct NetworkConfig {
     pub reward_mint: Pubkey,
     pub total_tasks: u64,
     pub total_reward_units: u64,
+    /// `pending_reward_units` per base unit of `reward_mint`, used by `PayoutReward`.
+    pub units_per_token: u64,
+    /// Minimum seconds between successive `ClaimByNode` calls for the same node.
+    pub claim_cooldown_secs: i64,
+    /// Authority proposed via `ProposeAuthority`, awaiting `AcceptAuthority`.
+    pub pending_authority: Option<Pubkey>,
+    /// Minimum seconds a `TaskRecord` must exist before it can be closed via `CloseTask`.
+    pub min_retention_secs: i64,
     pub bump: u8,
 }
 
 impl NetworkConfig {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 1;
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + (1 + 32) + 8 + 1;
 }
 
 /// Registered node (browser agent) state.
@@ -130,11 +298,13 @@ pub struct NodeAccount {
     pub completed_tasks: u64,
     pub pending_reward_units: u64,
     pub total_reward_units: u64,
+    /// Unix timestamp of the node's last successful `ClaimByNode`, or `0` if never claimed.
+    pub last_claim_ts: i64,
     pub bump: u8,
 }
 
 impl NodeAccount {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + 1;
 }
 
 /// Task submission record for audit.
@@ -151,6 +321,22 @@ impl TaskRecord {
     pub const LEN: usize = 32 + 32 + 8 + 8 + 1;
 }
 
+/// Commitment to a batch of task submissions, recorded as a single account instead of
+/// one `TaskRecord` per task. Individual tasks are verified on demand via `ProveTask`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BatchRecord {
+    pub node_identity: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u32,
+    pub total_reward_units: u64,
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+impl BatchRecord {
+    pub const LEN: usize = 32 + 32 + 4 + 8 + 8 + 1;
+}
+
 /// Program-specific errors.
 #[derive(Error, Debug, Copy, Clone)]
 pub enum MinionError {
@@ -162,6 +348,24 @@ pub enum MinionError {
     InsufficientPendingRewards,
     #[error("Account already initialized")]
     AccountAlreadyInitialized,
+    #[error("Reward mint mismatch")]
+    RewardMintMismatch,
+    #[error("Claim cooldown still active")]
+    ClaimCooldownActive,
+    #[error("The same account was passed in more than one program-owned slot")]
+    DuplicateAccount,
+    #[error("Node identity cannot be the same key as the network authority")]
+    NodeCannotBeAuthority,
+    #[error("No authority handover is pending")]
+    NoPendingAuthority,
+    #[error("Task record is younger than the minimum retention window")]
+    RetentionWindowNotElapsed,
+    #[error("Batch must contain at least one leaf and a positive reward total")]
+    EmptyBatch,
+    #[error("Merkle proof does not match the committed batch root")]
+    ProofVerificationFailed,
+    #[error("Reward units must be an exact multiple of units_per_token")]
+    RewardNotDivisible,
 }
 
 impl From<MinionError> for ProgramError {
@@ -175,6 +379,9 @@ fn process_init_network(
     accounts: &[AccountInfo],
     authority: Pubkey,
     reward_mint: Pubkey,
+    units_per_token: u64,
+    claim_cooldown_secs: i64,
+    min_retention_secs: i64,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let payer = next_account_info(account_info_iter)?;
@@ -185,7 +392,7 @@ fn process_init_network(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    if config_account.owner != program_id && !config_account.data_is_empty() {
+    if !config_account.data_is_empty() {
         return Err(MinionError::AccountAlreadyInitialized.into());
     }
 
@@ -202,37 +409,34 @@ fn process_init_network(
     let rent = Rent::get()?;
     let lamports = rent.minimum_balance(NetworkConfig::LEN);
 
-    if config_account.data_is_empty() {
-        let create_ix = system_instruction::create_account(
-            payer.key,
-            config_account.key,
-            lamports,
-            NetworkConfig::LEN as u64,
-            program_id,
-        );
-        invoke_signed(
-            &create_ix,
-            &[payer.clone(), config_account.clone(), system_program.clone()],
-            &[&[CONFIG_SEED, &[bump]]],
-        )?;
-    }
+    let create_ix = system_instruction::create_account(
+        payer.key,
+        config_account.key,
+        lamports,
+        NetworkConfig::LEN as u64,
+        program_id,
+    );
+    invoke_signed(
+        &create_ix,
+        &[
+            payer.clone(),
+            config_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[CONFIG_SEED, &[bump]]],
+    )?;
 
-    let mut config_data = if config_account.owner == program_id && !config_account.data_is_empty()
-    {
-        NetworkConfig::try_from_slice(&config_account.data.borrow())
-            .map_err(|_| ProgramError::InvalidAccountData)?
-    } else {
-        NetworkConfig {
-            authority,
-            reward_mint,
-            total_tasks: 0,
-            total_reward_units: 0,
-            bump,
-        }
+    let config_data = NetworkConfig {
+        authority,
+        reward_mint,
+        total_tasks: 0,
+        total_reward_units: 0,
+        units_per_token,
+        claim_cooldown_secs,
+        pending_authority: None,
+        min_retention_secs,
+        bump,
     };
-    config_data.authority = authority;
-    config_data.reward_mint = reward_mint;
-    config_data.bump = bump;
 
     config_data
         .serialize(&mut *config_account.data.borrow_mut())
@@ -253,15 +457,17 @@ fn process_register_node(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
     if !node_identity.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
+    if node_identity.key == authority_account.key {
+        return Err(MinionError::NodeCannotBeAuthority.into());
+    }
+    assert_distinct_keys(&[config_account.key, node_pda.key])?;
 
+    assert_owned_by(config_account, program_id)?;
     let config = NetworkConfig::try_from_slice(&config_account.data.borrow())
         .map_err(|_| ProgramError::InvalidAccountData)?;
     if config.authority != *authority_account.key {
         return Err(MinionError::UnauthorizedAuthority.into());
     }
-    if config_account.owner != program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
 
     let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
     if expected_config_key != *config_account.key {
@@ -310,6 +516,7 @@ fn process_register_node(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
         completed_tasks: 0,
         pending_reward_units: 0,
         total_reward_units: 0,
+        last_claim_ts: 0,
         bump,
     };
 
@@ -340,14 +547,15 @@ fn process_submit_task(
         return Err(ProgramError::InvalidInstructionData);
     }
 
+    assert_distinct_keys(&[node_pda.key, config_account.key, task_pda.key])?;
+    assert_owned_by(node_pda, program_id)?;
+    assert_owned_by(config_account, program_id)?;
+
     let mut node_state = NodeAccount::try_from_slice(&node_pda.data.borrow())
         .map_err(|_| ProgramError::InvalidAccountData)?;
     if node_state.node_identity != *node_identity.key {
         return Err(MinionError::NodeIdentityMismatch.into());
     }
-    if node_pda.owner != program_id || config_account.owner != program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
 
     let mut config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
         .map_err(|_| ProgramError::InvalidAccountData)?;
@@ -459,14 +667,15 @@ fn process_claim_reward(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
+    assert_distinct_keys(&[config_account.key, node_pda.key])?;
+    assert_owned_by(config_account, program_id)?;
+    assert_owned_by(node_pda, program_id)?;
+
     let config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
         .map_err(|_| ProgramError::InvalidAccountData)?;
     if config_state.authority != *authority_account.key {
         return Err(MinionError::UnauthorizedAuthority.into());
     }
-    if config_account.owner != program_id || node_pda.owner != program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
 
     let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
     if expected_config_key != *config_account.key {
@@ -497,3 +706,627 @@ fn process_claim_reward(
 
     Ok(())
 }
+
+fn process_payout_reward(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reward_units: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let node_pda = next_account_info(account_info_iter)?;
+    let vault_token_account = next_account_info(account_info_iter)?;
+    let destination_token_account = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let vault_authority = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_distinct_keys(&[
+        config_account.key,
+        node_pda.key,
+        vault_token_account.key,
+        destination_token_account.key,
+    ])?;
+    assert_owned_by(config_account, program_id)?;
+    assert_owned_by(node_pda, program_id)?;
+
+    let config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if config_state.authority != *authority_account.key {
+        return Err(MinionError::UnauthorizedAuthority.into());
+    }
+
+    let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if expected_config_key != *config_account.key {
+        msg!("Config PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut node_state = NodeAccount::try_from_slice(&node_pda.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let (expected_node_pda, _) =
+        Pubkey::find_program_address(&[NODE_SEED, node_state.node_identity.as_ref()], program_id);
+    if expected_node_pda != *node_pda.key {
+        msg!("Node PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if reward_units > node_state.pending_reward_units {
+        return Err(MinionError::InsufficientPendingRewards.into());
+    }
+
+    transfer_reward_from_vault(
+        program_id,
+        &config_state,
+        vault_token_account,
+        destination_token_account,
+        reward_mint,
+        vault_authority,
+        token_program,
+        reward_units,
+    )?;
+
+    node_state.pending_reward_units = node_state
+        .pending_reward_units
+        .checked_sub(reward_units)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    node_state
+        .serialize(&mut *node_pda.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    Ok(())
+}
+
+/// Converts `reward_units` to token base units at the given `units_per_token` ratio.
+/// Rejects `reward_units` that aren't an exact multiple so the pending balance, which
+/// callers always debit by the full `reward_units`, never diverges from what is
+/// actually transferred.
+fn reward_units_to_token_amount(
+    reward_units: u64,
+    units_per_token: u64,
+) -> Result<u64, ProgramError> {
+    let remainder = reward_units
+        .checked_rem(units_per_token)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if remainder != 0 {
+        msg!("reward_units must be an exact multiple of units_per_token");
+        return Err(MinionError::RewardNotDivisible.into());
+    }
+    reward_units
+        .checked_div(units_per_token)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Validates the vault, mint and vault-authority PDA, then signs and issues an
+/// `spl_token::instruction::transfer` for `reward_units` converted via `units_per_token`.
+/// Shared by every instruction that settles rewards on-chain.
+fn transfer_reward_from_vault<'a>(
+    program_id: &Pubkey,
+    config_state: &NetworkConfig,
+    vault_token_account: &AccountInfo<'a>,
+    destination_token_account: &AccountInfo<'a>,
+    reward_mint: &AccountInfo<'a>,
+    vault_authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    reward_units: u64,
+) -> ProgramResult {
+    if *token_program.key != spl_token::id() {
+        msg!("Token program mismatch");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    assert_owned_by(vault_token_account, &spl_token::id())?;
+
+    if *reward_mint.key != config_state.reward_mint {
+        return Err(MinionError::RewardMintMismatch.into());
+    }
+
+    let vault_token_state = TokenAccount::unpack(&vault_token_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if vault_token_state.mint != config_state.reward_mint {
+        return Err(MinionError::RewardMintMismatch.into());
+    }
+
+    let (expected_vault_authority, vault_bump) =
+        Pubkey::find_program_address(&[VAULT_SEED], program_id);
+    if expected_vault_authority != *vault_authority.key {
+        msg!("Vault authority PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if vault_token_state.owner != *vault_authority.key {
+        msg!("Vault token account not owned by vault authority");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let token_amount = reward_units_to_token_amount(reward_units, config_state.units_per_token)?;
+
+    let transfer_ix = spl_token::instruction::transfer(
+        token_program.key,
+        vault_token_account.key,
+        destination_token_account.key,
+        vault_authority.key,
+        &[],
+        token_amount,
+    )?;
+    invoke_signed(
+        &transfer_ix,
+        &[
+            vault_token_account.clone(),
+            destination_token_account.clone(),
+            vault_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[VAULT_SEED, &[vault_bump]]],
+    )
+}
+
+fn process_claim_by_node(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reward_units: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let node_identity = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let node_pda = next_account_info(account_info_iter)?;
+    let vault_token_account = next_account_info(account_info_iter)?;
+    let destination_token_account = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let vault_authority = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    if !node_identity.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_distinct_keys(&[
+        config_account.key,
+        node_pda.key,
+        vault_token_account.key,
+        destination_token_account.key,
+    ])?;
+    assert_owned_by(config_account, program_id)?;
+    assert_owned_by(node_pda, program_id)?;
+
+    let config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if expected_config_key != *config_account.key {
+        msg!("Config PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut node_state = NodeAccount::try_from_slice(&node_pda.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if node_state.node_identity != *node_identity.key {
+        return Err(MinionError::NodeIdentityMismatch.into());
+    }
+
+    let (expected_node_pda, _) =
+        Pubkey::find_program_address(&[NODE_SEED, node_identity.key.as_ref()], program_id);
+    if expected_node_pda != *node_pda.key {
+        msg!("Node PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if reward_units > node_state.pending_reward_units {
+        return Err(MinionError::InsufficientPendingRewards.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if node_state.last_claim_ts != 0
+        && now.saturating_sub(node_state.last_claim_ts) < config_state.claim_cooldown_secs
+    {
+        return Err(MinionError::ClaimCooldownActive.into());
+    }
+
+    transfer_reward_from_vault(
+        program_id,
+        &config_state,
+        vault_token_account,
+        destination_token_account,
+        reward_mint,
+        vault_authority,
+        token_program,
+        reward_units,
+    )?;
+
+    node_state.pending_reward_units = node_state
+        .pending_reward_units
+        .checked_sub(reward_units)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    node_state.last_claim_ts = now;
+    node_state
+        .serialize(&mut *node_pda.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    Ok(())
+}
+
+fn process_propose_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_authority: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_owned_by(config_account, program_id)?;
+
+    let mut config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if config_state.authority != *authority_account.key {
+        return Err(MinionError::UnauthorizedAuthority.into());
+    }
+
+    let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if expected_config_key != *config_account.key {
+        msg!("Config PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    config_state.pending_authority = Some(new_authority);
+    config_state
+        .serialize(&mut *config_account.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    Ok(())
+}
+
+fn process_accept_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pending_authority_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    if !pending_authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_owned_by(config_account, program_id)?;
+
+    let mut config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if expected_config_key != *config_account.key {
+        msg!("Config PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    match config_state.pending_authority {
+        None => return Err(MinionError::NoPendingAuthority.into()),
+        Some(pending) if pending != *pending_authority_account.key => {
+            return Err(MinionError::UnauthorizedAuthority.into());
+        }
+        Some(_) => {}
+    }
+
+    config_state.authority = *pending_authority_account.key;
+    config_state.pending_authority = None;
+    config_state
+        .serialize(&mut *config_account.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    Ok(())
+}
+
+fn process_close_task(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let closer = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let task_pda = next_account_info(account_info_iter)?;
+    let refund_recipient = next_account_info(account_info_iter)?;
+
+    if !closer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    assert_distinct_keys(&[config_account.key, task_pda.key, refund_recipient.key])?;
+    assert_owned_by(config_account, program_id)?;
+    assert_owned_by(task_pda, program_id)?;
+
+    let config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if expected_config_key != *config_account.key {
+        msg!("Config PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let task_state = TaskRecord::try_from_slice(&task_pda.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (expected_task_pda, _) = Pubkey::find_program_address(
+        &[
+            TASK_SEED,
+            task_state.node_identity.as_ref(),
+            &task_state.task_hash,
+        ],
+        program_id,
+    );
+    if expected_task_pda != *task_pda.key {
+        msg!("Task PDA seeds mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if *closer.key != task_state.node_identity && *closer.key != config_state.authority {
+        return Err(MinionError::UnauthorizedAuthority.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if now.saturating_sub(task_state.submitted_at) < config_state.min_retention_secs {
+        return Err(MinionError::RetentionWindowNotElapsed.into());
+    }
+
+    let refund_lamports = task_pda.lamports();
+    **refund_recipient.lamports.borrow_mut() = refund_recipient
+        .lamports()
+        .checked_add(refund_lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **task_pda.lamports.borrow_mut() = 0;
+    task_pda.data.borrow_mut().fill(0);
+    task_pda.assign(&system_program::id());
+
+    Ok(())
+}
+
+fn process_submit_task_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    merkle_root: [u8; 32],
+    leaf_count: u32,
+    total_reward_units: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let node_identity = next_account_info(account_info_iter)?;
+    let node_pda = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let batch_pda = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !node_identity.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if leaf_count == 0 || total_reward_units == 0 {
+        msg!("Batch must have at least one leaf and a positive reward total");
+        return Err(MinionError::EmptyBatch.into());
+    }
+
+    assert_distinct_keys(&[node_pda.key, config_account.key, batch_pda.key])?;
+    assert_owned_by(node_pda, program_id)?;
+    assert_owned_by(config_account, program_id)?;
+
+    let mut node_state = NodeAccount::try_from_slice(&node_pda.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if node_state.node_identity != *node_identity.key {
+        return Err(MinionError::NodeIdentityMismatch.into());
+    }
+
+    let mut config_state = NetworkConfig::try_from_slice(&config_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (expected_node_pda, node_bump) =
+        Pubkey::find_program_address(&[NODE_SEED, node_identity.key.as_ref()], program_id);
+    if expected_node_pda != *node_pda.key || node_bump != node_state.bump {
+        msg!("Node PDA seeds mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (expected_config_key, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if expected_config_key != *config_account.key {
+        msg!("Config PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !batch_pda.data_is_empty() {
+        return Err(MinionError::AccountAlreadyInitialized.into());
+    }
+
+    let (expected_batch_pda, bump) = Pubkey::find_program_address(
+        &[BATCH_SEED, node_identity.key.as_ref(), &merkle_root],
+        program_id,
+    );
+    if expected_batch_pda != *batch_pda.key {
+        msg!("Batch PDA mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(BatchRecord::LEN);
+    let create_ix = system_instruction::create_account(
+        node_identity.key,
+        batch_pda.key,
+        lamports,
+        BatchRecord::LEN as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &create_ix,
+        &[
+            node_identity.clone(),
+            batch_pda.clone(),
+            system_program.clone(),
+        ],
+        &[&[
+            BATCH_SEED,
+            node_identity.key.as_ref(),
+            &merkle_root,
+            &[bump],
+        ]],
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let batch_record = BatchRecord {
+        node_identity: *node_identity.key,
+        merkle_root,
+        leaf_count,
+        total_reward_units,
+        submitted_at: now,
+        bump,
+    };
+    batch_record
+        .serialize(&mut *batch_pda.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    node_state.completed_tasks = node_state
+        .completed_tasks
+        .checked_add(leaf_count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    node_state.pending_reward_units = node_state
+        .pending_reward_units
+        .checked_add(total_reward_units)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    node_state.total_reward_units = node_state
+        .total_reward_units
+        .checked_add(total_reward_units)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    node_state
+        .serialize(&mut *node_pda.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    config_state.total_tasks = config_state
+        .total_tasks
+        .checked_add(leaf_count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    config_state.total_reward_units = config_state
+        .total_reward_units
+        .checked_add(total_reward_units)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    config_state
+        .serialize(&mut *config_account.data.borrow_mut())
+        .map_err(|_| ProgramError::AccountDataTooSmall)?;
+
+    Ok(())
+}
+
+/// Maximum Merkle proof depth; bounds `sibling_on_right` (a `u64` bitfield, one bit per
+/// level) so folding never shifts it out of range.
+const MAX_PROOF_DEPTH: usize = 64;
+
+/// Recomputes a Merkle root from `leaf` by folding each `proof` sibling upward, using
+/// bit `i` of `sibling_on_right` to decide whether sibling `i` is the right-hand node.
+fn recompute_merkle_root(
+    leaf: [u8; 32],
+    proof: &[[u8; 32]],
+    sibling_on_right: u64,
+) -> Result<[u8; 32], ProgramError> {
+    if proof.len() > MAX_PROOF_DEPTH {
+        msg!("Merkle proof exceeds maximum supported depth");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut node = leaf;
+    for (i, sibling) in proof.iter().enumerate() {
+        node = if (sibling_on_right >> i) & 1 == 1 {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    Ok(node)
+}
+
+fn process_prove_task(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    task_hash: [u8; 32],
+    reward_units: u64,
+    proof: Vec<[u8; 32]>,
+    sibling_on_right: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let batch_pda = next_account_info(account_info_iter)?;
+
+    assert_owned_by(batch_pda, program_id)?;
+
+    let batch_state = BatchRecord::try_from_slice(&batch_pda.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let leaf = keccak::hashv(&[
+        batch_state.node_identity.as_ref(),
+        &task_hash,
+        &reward_units.to_le_bytes(),
+    ])
+    .to_bytes();
+
+    let recomputed_root = recompute_merkle_root(leaf, &proof, sibling_on_right)?;
+    if recomputed_root != batch_state.merkle_root {
+        return Err(MinionError::ProofVerificationFailed.into());
+    }
+
+    msg!("Task proof verified against batch root");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reward_units_to_token_amount_converts_exact_multiples() {
+        assert_eq!(reward_units_to_token_amount(1_000, 100).unwrap(), 10);
+        assert_eq!(reward_units_to_token_amount(0, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn reward_units_to_token_amount_rejects_remainder() {
+        // 150 is not an exact multiple of 100: flooring would silently transfer 1 token
+        // while the caller still debits the full 150 pending_reward_units, destroying
+        // the 50-unit remainder. This must be rejected instead of floored.
+        assert!(reward_units_to_token_amount(150, 100).is_err());
+    }
+
+    #[test]
+    fn reward_units_to_token_amount_rejects_zero_units_per_token() {
+        assert!(reward_units_to_token_amount(100, 0).is_err());
+    }
+
+    #[test]
+    fn claim_by_node_inherits_exact_multiple_guard() {
+        // process_claim_by_node settles through the same transfer_reward_from_vault
+        // helper as process_payout_reward, so a self-claim for a non-exact reward_units
+        // must be rejected rather than debiting the node's pending balance for more
+        // than the tokens it actually receives.
+        assert!(reward_units_to_token_amount(250, 100).is_err());
+        assert_eq!(reward_units_to_token_amount(300, 100).unwrap(), 3);
+    }
+
+    #[test]
+    fn recompute_merkle_root_folds_left_and_right_siblings() {
+        let leaf = [1u8; 32];
+        let sibling = [2u8; 32];
+
+        let left_fold = recompute_merkle_root(leaf, &[sibling], 0).unwrap();
+        assert_eq!(left_fold, keccak::hashv(&[&sibling, &leaf]).to_bytes());
+
+        let right_fold = recompute_merkle_root(leaf, &[sibling], 1).unwrap();
+        assert_eq!(right_fold, keccak::hashv(&[&leaf, &sibling]).to_bytes());
+    }
+
+    #[test]
+    fn recompute_merkle_root_rejects_oversized_proof() {
+        // A proof longer than MAX_PROOF_DEPTH (64) would shift sibling_on_right by at
+        // least a u64's width, which panics in an overflow-checked build. It must be
+        // rejected before the fold loop instead.
+        let leaf = [0u8; 32];
+        let proof = vec![[0u8; 32]; MAX_PROOF_DEPTH + 1];
+        assert!(recompute_merkle_root(leaf, &proof, 0).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Cross-cutting account validation helpers shared by the instruction handlers that
+//! read program-owned accounts.
+//!
+//! These guard against the classic aliasing and owner-confusion failure modes: passing
+//! the same account into two distinct program-owned slots, or trusting deserialized
+//! account data before confirming the account is actually owned by this program.
+
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::MinionError;
+
+/// Rejects the instruction if any two of `keys` are the same `Pubkey`.
+///
+/// Callers pass every program-owned account slot for the instruction so that account
+/// aliasing (e.g. passing the node PDA where the config PDA is expected) is caught
+/// before any account data is trusted.
+pub fn assert_distinct_keys(keys: &[&Pubkey]) -> Result<(), ProgramError> {
+    for i in 0..keys.len() {
+        for j in (i + 1)..keys.len() {
+            if keys[i] == keys[j] {
+                return Err(MinionError::DuplicateAccount.into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rejects the instruction if `account` is not owned by `program_id`.
+///
+/// Must be called before a program-owned account's data is deserialized, so that a
+/// forged or foreign-owned account can never masquerade as valid program state.
+pub fn assert_owned_by(account: &AccountInfo, program_id: &Pubkey) -> Result<(), ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_distinct_keys_accepts_all_unique() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        let c = Pubkey::new_unique();
+        assert!(assert_distinct_keys(&[&a, &b, &c]).is_ok());
+    }
+
+    #[test]
+    fn assert_distinct_keys_rejects_any_alias() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+        assert!(assert_distinct_keys(&[&a, &b, &a]).is_err());
+    }
+
+    #[test]
+    fn assert_owned_by_accepts_matching_owner() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = [];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert!(assert_owned_by(&account, &program_id).is_ok());
+    }
+
+    #[test]
+    fn assert_owned_by_rejects_foreign_owner() {
+        let program_id = Pubkey::new_unique();
+        let foreign_owner = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = [];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &foreign_owner,
+            false,
+            0,
+        );
+        assert!(assert_owned_by(&account, &program_id).is_err());
+    }
+}